@@ -0,0 +1,206 @@
+use anyhow::{Context, Result};
+use prometheus::BasicAuthentication;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Gateway configuration, loaded from a TOML or JSON file and overridable
+/// via environment variables, so deployments can point the gateway at a
+/// new cluster or a secured PushGateway/remote-write endpoint without a
+/// recompile.
+///
+/// `Debug` is implemented by hand rather than derived so that `password`
+/// is redacted instead of leaking into logs.
+#[derive(Clone, Deserialize)]
+pub struct Config {
+    #[serde(default = "default_redpanda_nodes")]
+    pub redpanda_nodes: Vec<RedpandaNode>,
+    #[serde(default)]
+    pub vm_targets: Vec<String>,
+    #[serde(default = "default_push_gateway_endpoint")]
+    pub push_gateway_endpoint: String,
+    #[serde(default)]
+    pub otlp_endpoints: Vec<String>,
+    #[serde(default = "default_scrape_interval_secs")]
+    pub scrape_interval_secs: u64,
+    #[serde(default = "default_enable_scrape_server")]
+    pub enable_scrape_server: bool,
+    #[serde(default = "default_scrape_server_addr")]
+    pub scrape_server_addr: String,
+    #[serde(default = "default_job_name")]
+    pub job_name: String,
+    #[serde(default)]
+    pub grouping_labels: HashMap<String, String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default = "default_sink")]
+    pub sink: MetricsSink,
+    #[serde(default = "default_consumer_group")]
+    pub consumer_group: String,
+    #[serde(default = "default_monitored_topic")]
+    pub monitored_topic: String,
+    #[serde(default)]
+    pub enable_kafka_reporter: bool,
+    #[serde(default = "default_kafka_reporter_topic")]
+    pub kafka_reporter_topic: String,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("redpanda_nodes", &self.redpanda_nodes)
+            .field("vm_targets", &self.vm_targets)
+            .field("push_gateway_endpoint", &self.push_gateway_endpoint)
+            .field("otlp_endpoints", &self.otlp_endpoints)
+            .field("scrape_interval_secs", &self.scrape_interval_secs)
+            .field("enable_scrape_server", &self.enable_scrape_server)
+            .field("scrape_server_addr", &self.scrape_server_addr)
+            .field("job_name", &self.job_name)
+            .field("grouping_labels", &self.grouping_labels)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***redacted***"))
+            .field("sink", &self.sink)
+            .field("consumer_group", &self.consumer_group)
+            .field("monitored_topic", &self.monitored_topic)
+            .field("enable_kafka_reporter", &self.enable_kafka_reporter)
+            .field("kafka_reporter_topic", &self.kafka_reporter_topic)
+            .finish()
+    }
+}
+
+/// Which backend(s) a collection cycle should export gathered metrics to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricsSink {
+    PushGateway,
+    Otlp,
+    Both,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RedpandaNode {
+    pub name: String,
+    pub ip: String,
+}
+
+fn default_redpanda_nodes() -> Vec<RedpandaNode> {
+    vec![
+        RedpandaNode { name: "redpanda-1".to_string(), ip: "172.16.192.110".to_string() },
+        RedpandaNode { name: "redpanda-2".to_string(), ip: "172.16.192.111".to_string() },
+        RedpandaNode { name: "redpanda-3".to_string(), ip: "172.16.192.112".to_string() },
+    ]
+}
+
+fn default_push_gateway_endpoint() -> String {
+    "http://localhost:9091".to_string()
+}
+
+fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+fn default_enable_scrape_server() -> bool {
+    true
+}
+
+fn default_scrape_server_addr() -> String {
+    "0.0.0.0:9464".to_string()
+}
+
+fn default_sink() -> MetricsSink {
+    MetricsSink::PushGateway
+}
+
+fn default_job_name() -> String {
+    "redpanda_metrics".to_string()
+}
+
+fn default_consumer_group() -> String {
+    "metrics_group".to_string()
+}
+
+fn default_monitored_topic() -> String {
+    "metrics".to_string()
+}
+
+fn default_kafka_reporter_topic() -> String {
+    "gateway_metrics".to_string()
+}
+
+impl Config {
+    /// Loads configuration from `path` (TOML unless the extension is
+    /// `.json`), then applies environment overrides on top.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("gagal membaca berkas konfigurasi {}", path.display()))?;
+
+        let mut config: Config = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            serde_json::from_str(&contents)
+                .with_context(|| format!("gagal mem-parsing konfigurasi JSON {}", path.display()))?
+        } else {
+            toml::from_str(&contents)
+                .with_context(|| format!("gagal mem-parsing konfigurasi TOML {}", path.display()))?
+        };
+
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
+    /// Falls back to the repo's historical defaults when no config file
+    /// is present, still honoring environment overrides.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::load(path)
+        } else {
+            let mut config = Config {
+                redpanda_nodes: default_redpanda_nodes(),
+                vm_targets: Vec::new(),
+                push_gateway_endpoint: default_push_gateway_endpoint(),
+                otlp_endpoints: Vec::new(),
+                scrape_interval_secs: default_scrape_interval_secs(),
+                enable_scrape_server: default_enable_scrape_server(),
+                scrape_server_addr: default_scrape_server_addr(),
+                job_name: default_job_name(),
+                grouping_labels: HashMap::from([("instance".to_string(), "localhost".to_string())]),
+                username: None,
+                password: None,
+                sink: default_sink(),
+                consumer_group: default_consumer_group(),
+                monitored_topic: default_monitored_topic(),
+                enable_kafka_reporter: false,
+                kafka_reporter_topic: default_kafka_reporter_topic(),
+            };
+            config.apply_env_overrides();
+            Ok(config)
+        }
+    }
+
+    fn apply_env_overrides(&mut self) {
+        if let Ok(endpoint) = std::env::var("PUSH_GATEWAY_ENDPOINT") {
+            self.push_gateway_endpoint = endpoint;
+        }
+        if let Ok(username) = std::env::var("PUSH_GATEWAY_USERNAME") {
+            self.username = Some(username);
+        }
+        if let Ok(password) = std::env::var("PUSH_GATEWAY_PASSWORD") {
+            self.password = Some(password);
+        }
+    }
+
+    /// Builds the `BasicAuthentication` to pass into `push_metrics`, if
+    /// both a username and password are configured.
+    pub fn basic_auth(&self) -> Option<BasicAuthentication> {
+        match (&self.username, &self.password) {
+            (Some(username), Some(password)) => Some(BasicAuthentication {
+                username: username.clone(),
+                password: password.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::{anyhow, Result};
+
+/// Cumulative jiffy counters read off the first `cpu` line of `/proc/stat`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuSample {
+    pub idle: u64,
+    pub total: u64,
+}
+
+/// Parses the aggregate `cpu` line of `/proc/stat` into idle vs. total
+/// jiffies. `idle` here is `idle + iowait`, matching the usual "CPU busy
+/// percentage" definition used by tools like `top`.
+pub fn parse_proc_stat(contents: &str) -> Result<CpuSample> {
+    let line = contents
+        .lines()
+        .find(|line| line.starts_with("cpu "))
+        .ok_or_else(|| anyhow!("baris 'cpu' tidak ditemukan di /proc/stat"))?;
+
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|field| field.parse::<u64>().ok())
+        .collect();
+
+    if fields.len() < 4 {
+        return Err(anyhow!("format /proc/stat tidak terduga: {}", line));
+    }
+
+    let idle = fields[3] + fields.get(4).copied().unwrap_or(0);
+    let total: u64 = fields.iter().sum();
+
+    Ok(CpuSample { idle, total })
+}
+
+/// Returns the CPU busy percentage between two samples taken some time
+/// apart, or `None` if the total jiffy count did not advance between them.
+pub fn cpu_busy_percent(prev: CpuSample, curr: CpuSample) -> Option<f64> {
+    let idle_delta = curr.idle.saturating_sub(prev.idle) as f64;
+    let total_delta = curr.total.saturating_sub(prev.total) as f64;
+
+    if total_delta <= 0.0 {
+        return None;
+    }
+
+    Some((1.0 - idle_delta / total_delta) * 100.0)
+}
+
+/// Parses `MemTotal` and `MemAvailable` (both in kB) out of
+/// `/proc/meminfo` and returns the used-memory percentage.
+pub fn memory_used_percent(contents: &str) -> Result<f64> {
+    let mem_total = find_meminfo_value(contents, "MemTotal")
+        .ok_or_else(|| anyhow!("MemTotal tidak ditemukan di /proc/meminfo"))?;
+    let mem_available = find_meminfo_value(contents, "MemAvailable")
+        .ok_or_else(|| anyhow!("MemAvailable tidak ditemukan di /proc/meminfo"))?;
+
+    if mem_total <= 0.0 {
+        return Err(anyhow!("MemTotal bernilai nol"));
+    }
+
+    Ok((mem_total - mem_available) / mem_total * 100.0)
+}
+
+fn find_meminfo_value(contents: &str, key: &str) -> Option<f64> {
+    contents.lines().find_map(|line| {
+        let rest = line.strip_prefix(key)?.trim_start().strip_prefix(':')?;
+        rest.trim().split_whitespace().next()?.parse::<f64>().ok()
+    })
+}
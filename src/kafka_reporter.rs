@@ -0,0 +1,57 @@
+use anyhow::Result;
+use prometheus::proto::MetricFamily;
+use prometheus::{Encoder, TextEncoder};
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+
+/// Reports each scrape's gathered metrics back into the cluster it
+/// monitors by producing the Prometheus text-exposition payload to a
+/// Kafka topic, one message per collection cycle. This is independent of
+/// (and can run alongside) the PushGateway/OTLP sinks.
+pub struct KafkaReporter {
+    producer: FutureProducer,
+    topic: String,
+    delivery_timeout: Duration,
+}
+
+impl KafkaReporter {
+    /// Builds a reporter that produces to `topic` via `broker`.
+    pub fn new(broker: &str, topic: &str, delivery_timeout: Duration) -> Result<Self> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", broker)
+            .set("message.timeout.ms", delivery_timeout.as_millis().to_string())
+            .create()?;
+
+        Ok(Self {
+            producer,
+            topic: topic.to_string(),
+            delivery_timeout,
+        })
+    }
+
+    /// Encodes `metric_families` as Prometheus text exposition and
+    /// produces them as a single message keyed by the grouping key
+    /// (typically the `instance`/job label used for the push/OTLP sinks).
+    pub async fn report(&self, metric_families: &[MetricFamily], grouping_key: &str) -> Result<()> {
+        let encoder = TextEncoder::new();
+        let mut payload = Vec::new();
+        encoder.encode(metric_families, &mut payload)?;
+
+        let record = FutureRecord::to(&self.topic)
+            .payload(&payload)
+            .key(grouping_key);
+
+        self.producer
+            .send(record, self.delivery_timeout)
+            .await
+            .map_err(|(e, _)| anyhow::anyhow!("gagal mengirim metrik ke topik {}: {}", self.topic, e))?;
+
+        println!(
+            "Metrik berhasil dilaporkan ke topik Kafka {} dengan kunci {}",
+            self.topic, grouping_key
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+pub mod config;
+pub mod host_metrics;
+pub mod kafka_reporter;
+pub mod otlp;
+pub mod parser;
+pub mod server;
+
+use anyhow::Result;
+use parser::parse_exposition;
+use prometheus::{opts, labels, Counter, Gauge, IntGauge, Registry};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::topic_partition_list::TopicPartitionList;
+use rdkafka::Offset;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct MetricsCollector {
+    // Internal metrik Redpanda
+    pub broker_up: IntGauge,
+    pub message_throughput: Counter,
+    pub consumer_lag: Gauge,
+    pub partition_count: IntGauge,
+    pub replica_count: IntGauge,
+    // External VM metrik
+    pub cpu_usage: Gauge,
+    pub memory_usage: Gauge,
+    // Sampel jiffy CPU sebelumnya, dipakai untuk hitung delta busy% host lokal
+    prev_cpu_sample: Arc<Mutex<Option<host_metrics::CpuSample>>>,
+    // Nilai kumulatif redpanda_kafka_request_bytes_total per broker dari
+    // scrape sebelumnya, dipakai untuk menghitung delta throughput alih-alih
+    // menumpuk ulang nilai counter mentahnya di setiap siklus
+    prev_throughput_sample: Arc<Mutex<HashMap<String, f64>>>,
+}
+
+impl MetricsCollector {
+    pub fn new(registry: &Registry) -> Result<Self> {
+        let broker_up = IntGauge::with_opts(opts!(
+            "redpanda_broker_up",
+            "Broker availability status",
+            labels! {"cluster" => "production"}
+        ))?;
+
+        let message_throughput = Counter::with_opts(opts!(
+            "redpanda_message_throughput",
+            "Message throughput rate",
+            labels! {"type" => "messages"}
+        ))?;
+
+        let consumer_lag = Gauge::with_opts(opts!(
+            "redpanda_consumer_lag",
+            "Consumer group lag",
+            labels! {"group" => "metrics_group"}
+        ))?;
+
+        let partition_count = IntGauge::with_opts(opts!(
+            "redpanda_partition_count",
+            "Number of partitions",
+            labels! {"topic" => "metrics"}
+        ))?;
+
+        let replica_count = IntGauge::with_opts(opts!(
+            "redpanda_replica_count",
+            "Number of replicas",
+            labels! {"topic" => "metrics"}
+        ))?;
+
+        let cpu_usage = Gauge::with_opts(opts!(
+            "vm_cpu_usage",
+            "CPU usage percentage",
+            labels! {"source" => "vm"}
+        ))?;
+
+        let memory_usage = Gauge::with_opts(opts!(
+            "vm_memory_usage",
+            "Memory usage percentage",
+            labels! {"source" => "vm"}
+        ))?;
+
+        registry.register(Box::new(broker_up.clone()))?;
+        registry.register(Box::new(message_throughput.clone()))?;
+        registry.register(Box::new(consumer_lag.clone()))?;
+        registry.register(Box::new(partition_count.clone()))?;
+        registry.register(Box::new(replica_count.clone()))?;
+        registry.register(Box::new(cpu_usage.clone()))?;
+        registry.register(Box::new(memory_usage.clone()))?;
+
+        Ok(Self {
+            broker_up,
+            message_throughput,
+            consumer_lag,
+            partition_count,
+            replica_count,
+            cpu_usage,
+            memory_usage,
+            prev_cpu_sample: Arc::new(Mutex::new(None)),
+            prev_throughput_sample: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    pub async fn collect_redpanda_metrics(&self, broker_ip: &str, client: &Client) -> Result<()> {
+        let url = format!("http://{}:9644/metrics", broker_ip);
+        println!("Mengakses metrik broker Redpanda dari URL: {}", url);
+
+        let response = client.get(&url).send().await;
+
+        match response {
+            Ok(resp) => {
+                if resp.status().is_success() {
+                    let body = resp.text().await?;
+                    let samples = parse_exposition(&body);
+                    // redpanda_kafka_request_bytes_total dipecah per
+                    // topik/jenis request; jumlahkan semua series alih-alih
+                    // mengambil satu series saja
+                    let cumulative = parser::sum_samples(&samples, "redpanda_kafka_request_bytes_total", &[]);
+
+                    let mut prev_samples = self.prev_throughput_sample.lock().unwrap();
+                    if let Some(&prev) = prev_samples.get(broker_ip) {
+                        let delta = (cumulative - prev).max(0.0);
+                        self.message_throughput.inc_by(delta);
+                    }
+                    prev_samples.insert(broker_ip.to_string(), cumulative);
+                    drop(prev_samples);
+
+                    println!("Metrik throughput broker {} berhasil dikumpulkan: {}", broker_ip, cumulative);
+                } else {
+                    eprintln!("Gagal mengakses metrik broker {}: Status {}", broker_ip, resp.status());
+                }
+            }
+            Err(err) => {
+                eprintln!("Error mengakses metrik broker {}: {}", broker_ip, err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remote node-exporter style mode: scrapes CPU/memory gauges off a
+    /// remote admin HTTP endpoint. Prefer `collect_host_metrics` for the
+    /// host this process itself runs on.
+    pub async fn collect_vm_metrics(&self, vm_ip: &str, client: &Client) -> Result<()> {
+        let url = format!("http://{}:9644/metrics", vm_ip);
+        println!("Mengakses metrik VM dari URL: {}", url);
+
+        let response = client.get(&url).send().await;
+
+        match response {
+            Ok(resp) => {
+                if resp.status().is_success() {
+                    let body = resp.text().await?;
+                    let samples = parse_exposition(&body);
+                    let cpu = parser::find_sample(&samples, "cpu_usage", &[])
+                        .map(|sample| sample.value)
+                        .unwrap_or(0.0);
+
+                    let memory = parser::find_sample(&samples, "memory_usage", &[])
+                        .map(|sample| sample.value)
+                        .unwrap_or(0.0);
+
+                    self.cpu_usage.set(cpu);
+                    self.memory_usage.set(memory);
+
+                    println!("Metrik VM {} berhasil dikumpulkan: CPU={}%, Memory={}%", vm_ip, cpu, memory);
+                } else {
+                    eprintln!("Gagal mengakses metrik VM {}: Status {}", vm_ip, resp.status());
+                }
+            }
+            Err(err) => {
+                eprintln!("Error mengakses metrik VM {}: {}", vm_ip, err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects genuine broker-side consumer lag, partition count and
+    /// replica count for `topic` via the Kafka protocol, as seen by
+    /// `group`. Partitions with no committed offset are treated as a full
+    /// backlog (lag = high watermark).
+    pub async fn collect_consumer_lag(&self, broker: &str, group: &str, topic: &str) -> Result<()> {
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", broker)
+            .set("group.id", group)
+            .create()?;
+
+        let fetch_timeout = Duration::from_secs(10);
+        let metadata = consumer.fetch_metadata(Some(topic), fetch_timeout)?;
+
+        let topic_metadata = match metadata.topics().iter().find(|t| t.name() == topic) {
+            Some(t) => t,
+            None => {
+                eprintln!("Topik {} tidak ditemukan di broker {}", topic, broker);
+                return Ok(());
+            }
+        };
+
+        let partitions = topic_metadata.partitions();
+        self.partition_count.set(partitions.len() as i64);
+
+        // Faktor replikasi seragam di semua partisi sebuah topik, jadi cukup
+        // ambil dari satu partisi saja alih-alih menjumlahkannya di semua
+        // partisi (yang hanya akan menghasilkan partition_count * replikasi)
+        let replica_total = partitions.first().map(|p| p.replicas().len()).unwrap_or(0);
+        self.replica_count.set(replica_total as i64);
+
+        let mut requested = TopicPartitionList::new();
+        for partition in partitions {
+            requested.add_partition(topic, partition.id());
+        }
+        let committed = consumer.committed_offsets(requested, fetch_timeout)?;
+
+        let mut total_lag: i64 = 0;
+        for partition in partitions {
+            let pid = partition.id();
+            let (_low_watermark, high_watermark) = consumer.fetch_watermarks(topic, pid, fetch_timeout)?;
+
+            let committed_offset = committed
+                .elements()
+                .iter()
+                .find(|e| e.partition() == pid)
+                .and_then(|e| match e.offset() {
+                    Offset::Offset(offset) => Some(offset),
+                    _ => None,
+                });
+
+            let lag = match committed_offset {
+                Some(offset) => (high_watermark - offset).max(0),
+                None => high_watermark,
+            };
+
+            total_lag += lag;
+        }
+
+        self.consumer_lag.set(total_lag as f64);
+        println!(
+            "Lag konsumen grup {} untuk topik {} berhasil dikumpulkan: {} (partisi={}, replika={})",
+            group, topic, total_lag, partitions.len(), replica_total
+        );
+
+        Ok(())
+    }
+
+    /// Gathers real local-host utilization straight from the kernel:
+    /// CPU busy percentage as the delta of non-idle jiffies over total
+    /// jiffies between two `/proc/stat` samples, and memory percentage as
+    /// `(MemTotal - MemAvailable) / MemTotal` from `/proc/meminfo`. This
+    /// is the default host metrics source; `collect_vm_metrics` remains
+    /// available for scraping a remote node-exporter-style endpoint.
+    pub async fn collect_host_metrics(&self) -> Result<()> {
+        let stat = tokio::fs::read_to_string("/proc/stat").await?;
+        let sample = host_metrics::parse_proc_stat(&stat)?;
+
+        let mut prev_sample = self.prev_cpu_sample.lock().unwrap();
+        if let Some(prev) = *prev_sample {
+            if let Some(cpu_percent) = host_metrics::cpu_busy_percent(prev, sample) {
+                self.cpu_usage.set(cpu_percent);
+            }
+        }
+        *prev_sample = Some(sample);
+        drop(prev_sample);
+
+        let meminfo = tokio::fs::read_to_string("/proc/meminfo").await?;
+        let memory_percent = host_metrics::memory_used_percent(&meminfo)?;
+        self.memory_usage.set(memory_percent);
+
+        println!(
+            "Metrik host lokal berhasil dikumpulkan: CPU={:.2}%, Memory={:.2}%",
+            self.cpu_usage.get(),
+            memory_percent
+        );
+
+        Ok(())
+    }
+}
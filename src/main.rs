@@ -1,194 +1,112 @@
 use anyhow::Result;
-use prometheus::{Registry, IntGauge, Gauge, Counter, opts, labels, Encoder, TextEncoder, BasicAuthentication};
-use rdkafka::config::ClientConfig;
-use rdkafka::consumer::{Consumer, StreamConsumer};
+use push_gateway::config::{Config, MetricsSink};
+use push_gateway::{kafka_reporter, otlp::OtlpExporter, server, MetricsCollector};
+use prometheus::Registry;
 use reqwest::Client;
-use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time;
-use rand::Rng;
 
-// Konfigurasi cluster Redpanda dan IP VM
-const REDPANDA_NODES: [(&str, &str); 3] = [
-    ("redpanda-1", "172.16.192.110"),
-    ("redpanda-2", "172.16.192.111"),
-    ("redpanda-3", "172.16.192.112"),
-];
+// Berkas konfigurasi TOML opsional; jika tidak ada, gateway
+// jatuh kembali ke default historis di `Config::load_or_default`
+const CONFIG_PATH: &str = "push-gateway.toml";
 
-#[derive(Clone)]
-struct MetricsCollector {
-    // Internal metrik Redpanda
-    broker_up: IntGauge,
-    message_throughput: Counter,
-    consumer_lag: Gauge,
-    partition_count: IntGauge,
-    replica_count: IntGauge,
-    // External VM metrik
-    cpu_usage: Gauge,
-    memory_usage: Gauge,
-}
-
-impl MetricsCollector {
-    fn new(registry: &Registry) -> Result<Self> {
-        let broker_up = IntGauge::with_opts(opts!(
-            "redpanda_broker_up",
-            "Broker availability status",
-            labels! {"cluster" => "production"}
-        ))?;
-
-        let message_throughput = Counter::with_opts(opts!(
-            "redpanda_message_throughput",
-            "Message throughput rate",
-            labels! {"type" => "messages"}
-        ))?;
-
-        let consumer_lag = Gauge::with_opts(opts!(
-            "redpanda_consumer_lag",
-            "Consumer group lag",
-            labels! {"group" => "metrics_group"}
-        ))?;
-
-        let partition_count = IntGauge::with_opts(opts!(
-            "redpanda_partition_count",
-            "Number of partitions",
-            labels! {"topic" => "metrics"}
-        ))?;
-
-        let replica_count = IntGauge::with_opts(opts!(
-            "redpanda_replica_count",
-            "Number of replicas",
-            labels! {"topic" => "metrics"}
-        ))?;
+// Timeout pengiriman reporter Kafka opsional; tidak perlu dikonfigurasi
+// per deployment seperti field-field lain di `Config`
+const KAFKA_REPORTER_TIMEOUT: Duration = Duration::from_secs(5);
 
-        let cpu_usage = Gauge::with_opts(opts!(
-            "vm_cpu_usage",
-            "CPU usage percentage",
-            labels! {"source" => "vm"}
-        ))?;
-
-        let memory_usage = Gauge::with_opts(opts!(
-            "vm_memory_usage",
-            "Memory usage percentage",
-            labels! {"source" => "vm"}
-        ))?;
-
-        registry.register(Box::new(broker_up.clone()))?;
-        registry.register(Box::new(message_throughput.clone()))?;
-        registry.register(Box::new(consumer_lag.clone()))?;
-        registry.register(Box::new(partition_count.clone()))?;
-        registry.register(Box::new(replica_count.clone()))?;
-        registry.register(Box::new(cpu_usage.clone()))?;
-        registry.register(Box::new(memory_usage.clone()))?;
+#[tokio::main]
+async fn main() -> Result<()> {
+    let config = Config::load_or_default(CONFIG_PATH)?;
 
-        Ok(Self {
-            broker_up,
-            message_throughput,
-            consumer_lag,
-            partition_count,
-            replica_count,
-            cpu_usage,
-            memory_usage,
-        })
+    let registry = Arc::new(Registry::new());
+    let metrics = MetricsCollector::new(&registry)?;
+    let client = Client::new();
+    let sink = config.sink;
+    let otlp_exporter = OtlpExporter::new(&config.otlp_endpoints)?;
+
+    if config.enable_scrape_server {
+        let scrape_registry = registry.clone();
+        let addr: SocketAddr = config.scrape_server_addr.parse()?;
+        tokio::spawn(async move {
+            if let Err(e) = server::serve(scrape_registry, addr).await {
+                eprintln!("Scrape server berhenti dengan error: {}", e);
+            }
+        });
     }
 
-    async fn collect_redpanda_metrics(&self, broker_ip: &str, client: &Client) -> Result<()> {
-        let url = format!("http://{}:9644/metrics", broker_ip);
-        println!("Mengakses metrik broker Redpanda dari URL: {}", url);
-
-        let response = client.get(&url).send().await;
-
-        match response {
-            Ok(resp) => {
-                if resp.status().is_success() {
-                    let body = resp.text().await?;
-                    let throughput: f64 = body.lines().find(|line| line.contains("message_throughput"))
-                        .and_then(|line| line.split('=').nth(1))
-                        .and_then(|value| value.trim().parse::<f64>().ok())
-                        .unwrap_or(0.0);
+    let kafka_reporter = match (config.enable_kafka_reporter, config.redpanda_nodes.first()) {
+        (true, Some(node)) => Some(kafka_reporter::KafkaReporter::new(
+            &format!("{}:9092", node.ip),
+            &config.kafka_reporter_topic,
+            KAFKA_REPORTER_TIMEOUT,
+        )?),
+        (true, None) => {
+            eprintln!("Kafka reporter dinonaktifkan: tidak ada redpanda_nodes yang dikonfigurasi");
+            None
+        }
+        (false, _) => None,
+    };
 
-                    self.message_throughput.inc_by(throughput);
-                    println!("Metrik throughput broker {} berhasil dikumpulkan: {}", broker_ip, throughput);
-                } else {
-                    eprintln!("Gagal mengakses metrik broker {}: Status {}", broker_ip, resp.status());
-                }
-            }
-            Err(err) => {
-                eprintln!("Error mengakses metrik broker {}: {}", broker_ip, err);
+    loop {
+        // proses ambil metrik dari broker Redpanda
+        for node in &config.redpanda_nodes {
+            if let Err(e) = metrics.collect_redpanda_metrics(&node.ip, &client).await {
+                eprintln!("Error collecting Redpanda metrics from {}: {}", node.ip, e);
             }
         }
 
-        Ok(())
-    }
-
-    async fn collect_vm_metrics(&self, vm_ip: &str, client: &Client) -> Result<()> {
-        let url = format!("http://{}:9644/metrics", vm_ip);
-        println!("Mengakses metrik VM dari URL: {}", url);
-
-        let response = client.get(&url).send().await;
-
-        match response {
-            Ok(resp) => {
-                if resp.status().is_success() {
-                    let body = resp.text().await?;
-                    let cpu: f64 = body.lines().find(|line| line.contains("cpu_usage"))
-                        .and_then(|line| line.split('=').nth(1))
-                        .and_then(|value| value.trim().parse::<f64>().ok())
-                        .unwrap_or(0.0);
-
-                    let memory: f64 = body.lines().find(|line| line.contains("memory_usage"))
-                        .and_then(|line| line.split('=').nth(1))
-                        .and_then(|value| value.trim().parse::<f64>().ok())
-                        .unwrap_or(0.0);
+        // proses ambil lag konsumen dari protokol Kafka langsung, jika ada
+        // node Redpanda yang dikonfigurasi (deployment VM/host-only yang
+        // tidak memantau Redpanda sama sekali boleh mengosongkan daftar ini)
+        if let Some(node) = config.redpanda_nodes.first() {
+            if let Err(e) = metrics
+                .collect_consumer_lag(&format!("{}:9092", node.ip), &config.consumer_group, &config.monitored_topic)
+                .await
+            {
+                eprintln!("Error collecting consumer lag from {}: {}", node.ip, e);
+            }
+        }
 
-                    self.cpu_usage.set(cpu);
-                    self.memory_usage.set(memory);
+        // proses ambil metrik CPU/memory dari host lokal tempat proses ini berjalan
+        if let Err(e) = metrics.collect_host_metrics().await {
+            eprintln!("Error collecting host metrics: {}", e);
+        }
 
-                    println!("Metrik VM {} berhasil dikumpulkan: CPU={}%, Memory={}%", vm_ip, cpu, memory);
-                } else {
-                    eprintln!("Gagal mengakses metrik VM {}: Status {}", vm_ip, resp.status());
-                }
-            }
-            Err(err) => {
-                eprintln!("Error mengakses metrik VM {}: {}", vm_ip, err);
+        // proses ambil metrik dari target VM/host jarak jauh, jika dikonfigurasi
+        for vm_target in &config.vm_targets {
+            if let Err(e) = metrics.collect_vm_metrics(vm_target, &client).await {
+                eprintln!("Error collecting VM metrics from {}: {}", vm_target, e);
             }
         }
 
-        Ok(())
-    }
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    let registry = Registry::new();
-    let metrics = MetricsCollector::new(&registry)?;
-    let client = Client::new();
+        let metric_families = registry.gather();
 
-    loop {
-        // proses ambil metrik dari broker Redpanda
-        for (_node_name, broker_ip) in REDPANDA_NODES.iter() {
-            if let Err(e) = metrics.collect_redpanda_metrics(broker_ip, &client).await {
-                eprintln!("Error collecting Redpanda metrics from {}: {}", broker_ip, e);
+        if let Some(reporter) = &kafka_reporter {
+            if let Err(e) = reporter.report(&metric_families, &config.job_name).await {
+                eprintln!("Error reporting metrics to Kafka: {}", e);
             }
         }
 
-        // Push metrik ke PushGateway
-        let push_gateway = "http://localhost:9091";
-        let metric_families = registry.gather();
-        let mut grouping = HashMap::new();
-        grouping.insert("instance".to_string(), "localhost".to_string());
+        if sink == MetricsSink::PushGateway || sink == MetricsSink::Both {
+            if let Err(e) = prometheus::push_metrics(
+                &config.job_name,
+                config.grouping_labels.clone(),
+                &config.push_gateway_endpoint,
+                metric_families.clone(),
+                config.basic_auth(),
+            ) {
+                eprintln!("Failed to push metrics: {}", e);
+            } else {
+                println!("Metrik berhasil dikirim ke PushGateway");
+            }
+        }
 
-        if let Err(e) = prometheus::push_metrics(
-            "redpanda_metrics",
-            grouping,
-            push_gateway,
-            metric_families,
-            None,
-        ) {
-            eprintln!("Failed to push metrics: {}", e);
-        } else {
-            println!("Metrik berhasil dikirim ke PushGateway");
+        if sink == MetricsSink::Otlp || sink == MetricsSink::Both {
+            otlp_exporter.export(&metric_families);
         }
 
-        time::sleep(Duration::from_secs(15)).await;
+        time::sleep(Duration::from_secs(config.scrape_interval_secs)).await;
     }
 }
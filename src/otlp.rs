@@ -0,0 +1,89 @@
+use anyhow::Result;
+use opentelemetry::metrics::{Meter, MeterProvider};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{MetricExporter, WithExportConfig};
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use prometheus::proto::MetricFamily;
+
+/// Translates gathered Prometheus `MetricFamily` values into OTLP metrics
+/// and exports them to every configured collector endpoint, so the
+/// gateway can feed an OpenTelemetry-based stack without a PushGateway
+/// deployment in front of it.
+///
+/// One exporter/meter-provider pipeline is built per endpoint at
+/// construction time and reused for the life of the process; building it
+/// is comparatively expensive (it opens an HTTP client and registers a
+/// periodic-export background task), so it must not be rebuilt every
+/// collection cycle.
+pub struct OtlpExporter {
+    endpoints: Vec<(String, SdkMeterProvider, Meter)>,
+}
+
+impl OtlpExporter {
+    /// Builds one OTLP/HTTP metrics pipeline per endpoint in
+    /// `metric_endpoints`. An endpoint whose pipeline fails to build is
+    /// logged and skipped rather than failing startup for the rest.
+    pub fn new(metric_endpoints: &[String]) -> Result<Self> {
+        let mut endpoints = Vec::new();
+        for endpoint in metric_endpoints {
+            match build_pipeline(endpoint) {
+                Ok((provider, meter)) => endpoints.push((endpoint.clone(), provider, meter)),
+                Err(e) => eprintln!("Gagal membangun pipeline OTLP untuk {}: {}", endpoint, e),
+            }
+        }
+
+        Ok(Self { endpoints })
+    }
+
+    /// Records `metric_families` against each endpoint's long-lived meter
+    /// and flushes the reading out over OTLP.
+    pub fn export(&self, metric_families: &[MetricFamily]) {
+        for (endpoint, provider, meter) in &self.endpoints {
+            for family in metric_families {
+                record_family(meter, family);
+            }
+
+            if let Err(e) = provider.force_flush() {
+                eprintln!("Gagal mengekspor metrik OTLP ke {}: {}", endpoint, e);
+            } else {
+                println!("Metrik berhasil diekspor via OTLP ke {}", endpoint);
+            }
+        }
+    }
+}
+
+fn build_pipeline(endpoint: &str) -> Result<(SdkMeterProvider, Meter)> {
+    let exporter = MetricExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+
+    let provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter)
+        .build();
+
+    let meter = provider.meter("push-gateway");
+    Ok((provider, meter))
+}
+
+fn record_family(meter: &Meter, family: &MetricFamily) {
+    let gauge = meter.f64_gauge(family.get_name().to_string()).build();
+
+    for metric in family.get_metric() {
+        let value = if metric.has_gauge() {
+            metric.get_gauge().get_value()
+        } else if metric.has_counter() {
+            metric.get_counter().get_value()
+        } else {
+            continue;
+        };
+
+        let attributes: Vec<KeyValue> = metric
+            .get_label()
+            .iter()
+            .map(|pair| KeyValue::new(pair.get_name().to_string(), pair.get_value().to_string()))
+            .collect();
+
+        gauge.record(value, &attributes);
+    }
+}
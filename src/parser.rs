@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+/// A single sample parsed out of a Prometheus text-exposition body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSample {
+    pub name: String,
+    pub labels: HashMap<String, String>,
+    pub value: f64,
+}
+
+/// Parses a Prometheus text-exposition payload (the format served by
+/// Redpanda's `:9644/metrics` admin endpoint) into a flat list of samples.
+///
+/// Lines starting with `#` (`# HELP` / `# TYPE` / comments) are skipped.
+/// Each remaining line is `metric_name{label="v",...} value [timestamp]`,
+/// where the label block is optional and the value may be `NaN`, `+Inf`
+/// or `-Inf` per the exposition format spec.
+pub fn parse_exposition(body: &str) -> Vec<ParsedSample> {
+    let mut samples = Vec::new();
+
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(sample) = parse_line(line) {
+            samples.push(sample);
+        }
+    }
+
+    samples
+}
+
+fn parse_line(line: &str) -> Option<ParsedSample> {
+    let (name_and_labels, rest) = match line.find(char::is_whitespace) {
+        Some(idx) => (&line[..idx], line[idx..].trim_start()),
+        None => return None,
+    };
+
+    // Value is the first whitespace-separated token after the name/labels;
+    // an optional timestamp may follow but we don't need it here.
+    let value_str = rest.split_whitespace().next()?;
+    let value = parse_float(value_str)?;
+
+    let (name, labels) = match name_and_labels.find('{') {
+        Some(brace) => {
+            let name = name_and_labels[..brace].to_string();
+            let label_block = name_and_labels[brace + 1..].trim_end_matches('}');
+            (name, parse_labels(label_block))
+        }
+        None => (name_and_labels.to_string(), HashMap::new()),
+    };
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(ParsedSample { name, labels, value })
+}
+
+fn parse_labels(block: &str) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+    if block.trim().is_empty() {
+        return labels;
+    }
+
+    for pair in split_label_pairs(block) {
+        if let Some(eq) = pair.find('=') {
+            let key = pair[..eq].trim().to_string();
+            let value = pair[eq + 1..].trim().trim_matches('"').to_string();
+            if !key.is_empty() {
+                labels.insert(key, value);
+            }
+        }
+    }
+
+    labels
+}
+
+/// Splits a label block on top-level commas, ignoring commas inside
+/// quoted label values.
+fn split_label_pairs(block: &str) -> Vec<&str> {
+    let mut pairs = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+
+    for (idx, ch) in block.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                pairs.push(&block[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    pairs.push(&block[start..]);
+
+    pairs
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+    match value {
+        "NaN" => Some(f64::NAN),
+        "+Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        _ => value.parse::<f64>().ok(),
+    }
+}
+
+/// Finds the first sample with an exact metric name match and, if given,
+/// an exact match on every requested label.
+///
+/// Only safe to use for metrics guaranteed to be exposed as a single
+/// series (or when `labels` fully pins down one specific series); a
+/// counter broken out by label combination (per topic, per request type,
+/// ...) has many samples sharing a name, and picking "the first" one
+/// silently drops the rest. Use `sum_samples` for those.
+pub fn find_sample<'a>(
+    samples: &'a [ParsedSample],
+    name: &str,
+    labels: &[(&str, &str)],
+) -> Option<&'a ParsedSample> {
+    samples.iter().find(|sample| {
+        sample.name == name
+            && labels
+                .iter()
+                .all(|(k, v)| sample.labels.get(*k).map(|lv| lv == v).unwrap_or(false))
+    })
+}
+
+/// Sums the values of every sample with an exact metric name match and,
+/// if given, an exact match on every requested label. Use this for
+/// metrics that Redpanda breaks out by label combination (e.g. per
+/// topic/request-type), where the intent is the aggregate across all
+/// series rather than one arbitrary series.
+pub fn sum_samples(samples: &[ParsedSample], name: &str, labels: &[(&str, &str)]) -> f64 {
+    samples
+        .iter()
+        .filter(|sample| {
+            sample.name == name
+                && labels
+                    .iter()
+                    .all(|(k, v)| sample.labels.get(*k).map(|lv| lv == v).unwrap_or(false))
+        })
+        .map(|sample| sample.value)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Pola realistis endpoint :9644/metrics Redpanda: beberapa baris
+    // HELP/TYPE, dan satu metrik counter yang dipecah per label topik +
+    // jenis request, persis kasus yang membuat `find_sample` salah ambil
+    // satu series saja.
+    const REDPANDA_BODY: &str = r#"
+# HELP redpanda_kafka_request_bytes_total Total bytes received from produce/fetch requests
+# TYPE redpanda_kafka_request_bytes_total counter
+redpanda_kafka_request_bytes_total{topic="metrics",request="produce"} 1024
+redpanda_kafka_request_bytes_total{topic="metrics",request="fetch"} 2048
+redpanda_kafka_request_bytes_total{topic="other_topic",request="produce"} 512
+# HELP redpanda_broker_up Broker availability
+# TYPE redpanda_broker_up gauge
+redpanda_broker_up 1
+"#;
+
+    #[test]
+    fn parse_exposition_skips_comments_and_parses_all_samples() {
+        let samples = parse_exposition(REDPANDA_BODY);
+
+        assert_eq!(samples.len(), 4);
+        assert_eq!(samples[0].name, "redpanda_kafka_request_bytes_total");
+        assert_eq!(samples[0].labels.get("topic").map(String::as_str), Some("metrics"));
+        assert_eq!(samples[0].value, 1024.0);
+    }
+
+    #[test]
+    fn find_sample_only_returns_first_matching_series() {
+        let samples = parse_exposition(REDPANDA_BODY);
+
+        // Sengaja menunjukkan keterbatasan `find_sample`: tanpa filter
+        // label yang cukup spesifik, ini hanya mengembalikan series
+        // pertama yang cocok nama-nya, bukan agregat semua series.
+        let first = find_sample(&samples, "redpanda_kafka_request_bytes_total", &[]).unwrap();
+        assert_eq!(first.value, 1024.0);
+
+        let produce_only = find_sample(
+            &samples,
+            "redpanda_kafka_request_bytes_total",
+            &[("request", "produce"), ("topic", "metrics")],
+        )
+        .unwrap();
+        assert_eq!(produce_only.value, 1024.0);
+    }
+
+    #[test]
+    fn sum_samples_aggregates_across_all_label_combinations() {
+        let samples = parse_exposition(REDPANDA_BODY);
+
+        let total = sum_samples(&samples, "redpanda_kafka_request_bytes_total", &[]);
+        assert_eq!(total, 1024.0 + 2048.0 + 512.0);
+    }
+
+    #[test]
+    fn sum_samples_respects_label_filter() {
+        let samples = parse_exposition(REDPANDA_BODY);
+
+        let metrics_topic_only = sum_samples(
+            &samples,
+            "redpanda_kafka_request_bytes_total",
+            &[("topic", "metrics")],
+        );
+        assert_eq!(metrics_topic_only, 1024.0 + 2048.0);
+    }
+
+    #[test]
+    fn sum_samples_returns_zero_when_nothing_matches() {
+        let samples = parse_exposition(REDPANDA_BODY);
+        assert_eq!(sum_samples(&samples, "nonexistent_metric", &[]), 0.0);
+    }
+}
@@ -0,0 +1,46 @@
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{Encoder, Registry, TextEncoder};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Serves the shared `Registry` as a pull-based Prometheus scrape target
+/// on `GET /metrics`, plus a `GET /healthz` liveness check. The collection
+/// loop keeps refreshing the registry in the background while this server
+/// always encodes whatever values are currently held.
+pub async fn serve(registry: Arc<Registry>, addr: SocketAddr) -> anyhow::Result<()> {
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(healthz_handler))
+        .with_state(registry);
+
+    println!("Melayani endpoint scrape /metrics di {}", addr);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+async fn metrics_handler(State(registry): State<Arc<Registry>>) -> impl IntoResponse {
+    let encoder = TextEncoder::new();
+    let metric_families = registry.gather();
+
+    let mut buffer = Vec::new();
+    if let Err(e) = encoder.encode(&metric_families, &mut buffer) {
+        eprintln!("Gagal meng-encode metrik: {}", e);
+        return (StatusCode::INTERNAL_SERVER_ERROR, [(header::CONTENT_TYPE, String::new())], String::new());
+    }
+
+    let content_type = encoder.format_type().to_string();
+    match String::from_utf8(buffer) {
+        Ok(body) => (StatusCode::OK, [(header::CONTENT_TYPE, content_type)], body),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, [(header::CONTENT_TYPE, String::new())], String::new()),
+    }
+}
+
+async fn healthz_handler() -> impl IntoResponse {
+    (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain")], "ok")
+}
@@ -1,19 +1,26 @@
 use push_gateway::MetricsCollector;
 use prometheus::{Registry, Encoder, TextEncoder};
+use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
+use rdkafka::client::DefaultClientContext;
+use rdkafka::config::ClientConfig;
+use rdkafka::mocking::MockCluster;
+use rdkafka::producer::{FutureProducer, FutureRecord};
 use reqwest::Client;
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_collect_redpanda_metrics() {
     // Buat registry dan collector untuk pengujian
     let registry = Registry::new();
     let metrics = MetricsCollector::new(&registry).unwrap();
+    let client = Client::new();
 
     // Gunakan broker Kafka dummy
     let broker = "localhost:9092";
 
     // Tes pengumpulan metrik internal Redpanda
-    let result = metrics.collect_redpanda_metrics(broker).await;
+    let result = metrics.collect_redpanda_metrics(broker, &client).await;
 
     // Pastikan hasilnya sukses atau error jika broker tidak aktif
     assert!(result.is_ok() || result.is_err());
@@ -72,6 +79,61 @@ async fn test_push_metrics_to_gateway() {
     assert!(result.is_ok() || result.is_err());
 }
 
+#[tokio::test]
+async fn test_collect_consumer_lag_with_mock_cluster() {
+    // Kluster Kafka mock in-process, tidak memerlukan broker eksternal
+    let mock_cluster = MockCluster::new(3).expect("gagal membuat mock cluster");
+    let bootstrap = mock_cluster.bootstrap_servers();
+
+    let topic = "mock_metrics_topic";
+    let num_partitions = 4;
+    let num_replicas = 3;
+
+    let admin: AdminClient<DefaultClientContext> = ClientConfig::new()
+        .set("bootstrap.servers", &bootstrap)
+        .create()
+        .expect("gagal membuat admin client");
+
+    let new_topic = NewTopic::new(topic, num_partitions, TopicReplication::Fixed(num_replicas));
+    admin
+        .create_topics(&[new_topic], &AdminOptions::new())
+        .await
+        .expect("gagal membuat topik");
+
+    let producer: FutureProducer = ClientConfig::new()
+        .set("bootstrap.servers", &bootstrap)
+        .create()
+        .expect("gagal membuat producer");
+
+    let message_count = 10;
+    for i in 0..message_count {
+        producer
+            .send(
+                FutureRecord::to(topic)
+                    .payload(&format!("msg-{}", i))
+                    .key(&format!("k{}", i)),
+                Duration::from_secs(5),
+            )
+            .await
+            .expect("gagal mengirim pesan");
+    }
+
+    let registry = Registry::new();
+    let metrics = MetricsCollector::new(&registry).unwrap();
+    let group = "mock_consumer_group";
+
+    // Grup belum pernah commit offset, jadi lag yang diharapkan adalah
+    // seluruh backlog: jumlah pesan yang baru saja diproduksi.
+    metrics
+        .collect_consumer_lag(&bootstrap, group, topic)
+        .await
+        .expect("gagal mengumpulkan consumer lag");
+
+    assert_eq!(metrics.partition_count.get(), num_partitions as i64);
+    assert_eq!(metrics.replica_count.get(), num_replicas as i64);
+    assert_eq!(metrics.consumer_lag.get(), message_count as f64);
+}
+
 #[tokio::test]
 async fn test_end_to_end_metrics_collection() {
     // Pengujian integrasi menyeluruh (end-to-end)
@@ -81,7 +143,7 @@ async fn test_end_to_end_metrics_collection() {
 
     // Pengumpulan metrik internal Redpanda
     for (_node_name, broker) in [("redpanda-1", "localhost:9092")].iter() {
-        let result = metrics.collect_redpanda_metrics(broker).await;
+        let result = metrics.collect_redpanda_metrics(broker, &client).await;
         assert!(result.is_ok() || result.is_err());
     }
 